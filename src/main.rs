@@ -8,7 +8,6 @@
 
 use polars::prelude::*;
 use classify::{get_jenks_classification, get_quantile_classification, get_equal_interval_classification, get_st_dev_classification, get_head_tail_classification};
-use ndhistogram::{Histogram, ndhistogram, axis::Variable};
 use clap::{Parser, ValueEnum};
 use serde::{Serialize, Deserialize};
 use std::fs::File;
@@ -37,6 +36,24 @@ struct NumericHistogramBin {
     min: Option<f64>,
     /// The max value in this bin
     max: Option<f64>,
+    /// The multinomial variance of this bin's count (`n * (1 - n/N)`)
+    variance: Option<f64>,
+    /// The standard error of this bin's count (square root of `variance`)
+    std_error: Option<f64>,
+    /// Probability density (`count / (N * bin_width)`) when `--normalize` is set
+    density: Option<f64>,
+    /// The midpoint of the bin, `(from + to) / 2`, when `--normalize` is set
+    center: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CategoricalHistogramBin {
+    /// The distinct category value
+    category: String,
+    /// The number of rows holding this category
+    count: usize,
+    /// The fraction of categorized (non-null) rows holding this category
+    fraction: f64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -50,6 +67,37 @@ struct HistogramMetadata {
     numeric_values: usize,
     null_values: usize,
     bin_edges: Vec<f64>,
+    /// Per-argument contribution breakdown: one entry per `--file` argument (a
+    /// glob argument is reported as a single combined entry, not per matched file)
+    per_file: Vec<FileBreakdown>,
+    /// Distribution statistics computed over the numeric values
+    summary: Option<DistributionSummary>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DistributionSummary {
+    min: f64,
+    max: f64,
+    mean: f64,
+    median: f64,
+    std_dev: f64,
+    percentiles: Vec<Percentile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Percentile {
+    /// The requested percentile, e.g. 90.0 for p90
+    percentile: f64,
+    /// The interpolated value at that percentile
+    value: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileBreakdown {
+    file: String,
+    total_rows: usize,
+    numeric_values: usize,
+    null_values: usize,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -58,6 +106,29 @@ struct HistogramResult {
     bins: Vec<NumericHistogramBin>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct CategoricalHistogramResult {
+    metadata: HistogramMetadata,
+    bins: Vec<CategoricalHistogramBin>,
+}
+
+/// Running count plus min/max for a single histogram slot, accumulated in one
+/// pass over the numeric values.
+#[derive(Default)]
+struct BinAccumulator {
+    count: usize,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl BinAccumulator {
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.min = Some(self.min.map_or(value, |m| m.min(value)));
+        self.max = Some(self.max.map_or(value, |m| m.max(value)));
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "binner",
@@ -83,9 +154,9 @@ struct Args {
     #[arg(long, default_value_t = 1.0, help = "Number of standard deviations for bin sizing")]
     std_dev_size: f64,
 
-    /// Path to the Parquet file to analyze
-    #[arg(short, long, help = "Path to the input Parquet file")]
-    file: String,
+    /// Path(s) to the Parquet file(s) to analyze (repeatable; each value may be a glob)
+    #[arg(short, long, num_args = 1.., help = "Path(s) to the input Parquet file(s). Repeat --file or pass a glob to aggregate across several files. The per-file breakdown reports one row per --file argument; a glob argument is summarized as a single row.")]
+    file: Vec<String>,
 
     /// List all available columns in the Parquet file and exit
     #[arg(long, help = "Show available columns in the file and exit")]
@@ -98,16 +169,36 @@ struct Args {
     /// Output file path for JSON results (prints to stdout if not specified)
     #[arg(short, long, help = "File path to write JSON results (optional)")]
     output: Option<String>,
+
+    /// Augment each numeric bin with a probability density and center for comparing unequal-width bins
+    #[arg(long, help = "Add density (count / (N * bin_width)) and center fields to numeric bins")]
+    normalize: bool,
+
+    /// Percentiles to report in the metadata summary (comma-separated, defaults to 25,50,75,90,99)
+    #[arg(long, value_delimiter = ',', help = "Percentiles for the distribution summary (comma-separated, 0-100)")]
+    percentiles: Option<Vec<f64>>,
+
+    /// Summarize a string/boolean/enum column as a sparse category -> count map instead of a numeric histogram
+    #[arg(long, help = "Build a categorical (category -> count) histogram instead of a numeric one")]
+    categorical: bool,
+
+    /// Keep only the N most frequent categories, collapsing the tail into an \"other\" bucket
+    #[arg(long, help = "Truncate categorical output to the top N categories (only with --categorical)")]
+    top_n: Option<usize>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
+    if args.file.is_empty() {
+        return Err("At least one --file must be provided".into());
+    }
+
     // If list_columns is specified, just show the columns and exit
     if args.list_columns {
-        let lf = LazyFrame::scan_parquet(&args.file, Default::default())?;
+        let lf = LazyFrame::scan_parquet(&args.file[0], Default::default())?;
         let df = lf.limit(0).collect()?; // Just get schema, no data
-        println!("Available columns in {}:", args.file);
+        println!("Available columns in {}:", args.file[0]);
         for (i, column_name) in df.get_column_names().iter().enumerate() {
             println!("  {}. {}", i + 1, column_name);
         }
@@ -115,43 +206,67 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Ensure required arguments are provided when not listing columns
-    let column = args.column.ok_or("Column name is required when not listing columns")?;
+    let column = args.column.clone().ok_or("Column name is required when not listing columns")?;
+
+    // Categorical mode summarizes string/boolean/enum columns as a sparse
+    // category -> count map rather than a numeric histogram.
+    if args.categorical {
+        return run_categorical(&args, &column);
+    }
 
     // Algorithm is only required if custom bins are not provided
     if args.bins.is_none() && args.algorithm.is_none() {
         return Err("Either algorithm or custom bins must be provided".into());
     }
 
-    // Read data using Polars lazy API
-    let lf = LazyFrame::scan_parquet(&args.file, Default::default())?
-        .select([col(&column)]);
-
-    let df = lf.collect()?;
-
-    // Extract the column and handle nulls
-    let series = df.column(&column)?;
+    // Read each --file argument independently so we can report its contribution
+    // (a glob argument is read in one scan, hence one combined breakdown entry),
+    // then concatenate the numeric values into a single vector. Every file is
+    // binned against identical bin_edges, so aggregation is just elementwise
+    // addition of counts with a min/max reduction.
     let mut numeric_values = Vec::new();
-
-    // Convert to ChunkedArray to iterate over values
-    for i in 0..series.len() {
-        if let Ok(av) = series.get(i) {
-            match av {
-                AnyValue::Float64(f) => numeric_values.push(f),
-                AnyValue::Float32(f) => numeric_values.push(f as f64),
-                AnyValue::Int64(i) => numeric_values.push(i as f64),
-                AnyValue::Int32(i) => numeric_values.push(i as f64),
-                AnyValue::Int16(i) => numeric_values.push(i as f64),
-                AnyValue::Int8(i) => numeric_values.push(i as f64),
-                AnyValue::UInt64(i) => numeric_values.push(i as f64),
-                AnyValue::UInt32(i) => numeric_values.push(i as f64),
-                AnyValue::UInt16(i) => numeric_values.push(i as f64),
-                AnyValue::UInt8(i) => numeric_values.push(i as f64),
-                _ => {}, // Skip nulls and non-numeric types
+    let mut per_file = Vec::new();
+    let mut total_rows = 0;
+
+    for file in &args.file {
+        let lf = LazyFrame::scan_parquet(file, Default::default())?
+            .select([col(&column)]);
+        let df = lf.collect()?;
+
+        let series = df.column(&column)?;
+        let file_numeric_start = numeric_values.len();
+
+        // Iterate over the series by index, keeping only numeric values
+        for i in 0..series.len() {
+            if let Ok(av) = series.get(i) {
+                match av {
+                    AnyValue::Float64(f) => numeric_values.push(f),
+                    AnyValue::Float32(f) => numeric_values.push(f as f64),
+                    AnyValue::Int64(i) => numeric_values.push(i as f64),
+                    AnyValue::Int32(i) => numeric_values.push(i as f64),
+                    AnyValue::Int16(i) => numeric_values.push(i as f64),
+                    AnyValue::Int8(i) => numeric_values.push(i as f64),
+                    AnyValue::UInt64(i) => numeric_values.push(i as f64),
+                    AnyValue::UInt32(i) => numeric_values.push(i as f64),
+                    AnyValue::UInt16(i) => numeric_values.push(i as f64),
+                    AnyValue::UInt8(i) => numeric_values.push(i as f64),
+                    _ => {}, // Skip nulls and non-numeric types
+                }
             }
         }
+
+        let file_numeric = numeric_values.len() - file_numeric_start;
+        let file_rows = df.height();
+        total_rows += file_rows;
+        per_file.push(FileBreakdown {
+            file: file.clone(),
+            total_rows: file_rows,
+            numeric_values: file_numeric,
+            null_values: file_rows - file_numeric,
+        });
     }
 
-    let null_count = df.height() - numeric_values.len();
+    let null_count = total_rows - numeric_values.len();
 
     if numeric_values.is_empty() {
         eprintln!("Error: No numeric values found in column '{}'", column);
@@ -219,18 +334,59 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         (calculated_breaks, false) // Algorithm-based bins don't include null bin by default
     };
 
-    // Create histogram using ndhistogram with Variable axis
-    // Variable axis automatically includes underflow and overflow bins
-    let mut hist = ndhistogram!(Variable::new(breaks.clone())?);
+    if breaks.len() < 2 {
+        return Err("At least two bin edges are required to build a histogram".into());
+    }
+
+    // Single pass over the values: binary-search the sorted edges to find each
+    // value's bin and update a per-slot accumulator. Slot 0 is the underflow
+    // bin (value < first edge), slots 1..=num_finite are the finite bins, and
+    // the last slot is the overflow bin (value >= last edge), matching
+    // ndhistogram's Variable-axis semantics. This is O(N log bins) rather than
+    // re-scanning every value once per bin.
+    let num_finite = breaks.len().saturating_sub(1);
+    let mut slots: Vec<BinAccumulator> = (0..num_finite + 2).map(|_| BinAccumulator::default()).collect();
 
-    // Fill histogram with values
     for &value in &numeric_values {
-        hist.fill(&value);
+        let slot = if breaks.is_empty() || value < breaks[0] {
+            0
+        } else if value >= breaks[breaks.len() - 1] {
+            slots.len() - 1
+        } else {
+            // partition_point gives the number of edges <= value (>= 1 here),
+            // so the finite bin index is that minus one, offset by the
+            // underflow slot.
+            breaks.partition_point(|&edge| edge <= value)
+        };
+        slots[slot].add(value);
     }
 
+    // Distribution summary over a sorted copy of the values, computed with a
+    // single sort so the percentiles and median share one ordering pass.
+    let total_numeric = numeric_values.len() as f64;
+    let mut sorted_values = numeric_values.clone();
+    sorted_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mean = numeric_values.iter().sum::<f64>() / total_numeric;
+    let variance_pop = numeric_values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / total_numeric;
+    let requested_percentiles = args.percentiles.clone().unwrap_or_else(|| vec![25.0, 50.0, 75.0, 90.0, 99.0]);
+    if let Some(&bad) = requested_percentiles.iter().find(|&&p| !(0.0..=100.0).contains(&p)) {
+        return Err(format!("Invalid percentile: {}. Percentiles must be in the range 0-100", bad).into());
+    }
+    let summary = DistributionSummary {
+        min: sorted_values[0],
+        max: sorted_values[sorted_values.len() - 1],
+        mean,
+        median: percentile_of(&sorted_values, 50.0),
+        std_dev: variance_pop.sqrt(),
+        percentiles: requested_percentiles.iter()
+            .map(|&p| Percentile { percentile: p, value: percentile_of(&sorted_values, p) })
+            .collect(),
+    };
+
     // Prepare metadata
     let metadata = HistogramMetadata {
-        file: args.file.clone(),
+        file: args.file.join(", "),
         column: column.clone(),
         algorithm: algorithm_used.as_ref().map(|a| format!("{:?}", a)),
         num_bins: if algorithm_used.is_some() { Some(args.num_bins) } else { None },
@@ -239,47 +395,59 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         } else {
             None
         },
-        total_rows: df.height(),
+        total_rows,
         numeric_values: numeric_values.len(),
         null_values: null_count,
         bin_edges: breaks.clone(),
+        per_file,
+        summary: Some(summary),
     };
 
-    // Build bins with min/max tracking
+    // Build bins directly from the accumulated slots.
     let mut bins = Vec::new();
 
-    for item in hist.iter() {
-        let count = *item.value as usize;
-
-        // Calculate min/max for this bin by filtering values
-        let (min_val, max_val, bin_label, from, to) = match &item.bin {
-            ndhistogram::axis::BinInterval::Underflow { end } => {
-                let values_in_bin: Vec<f64> = numeric_values.iter()
-                    .filter(|&&v| v < *end)
-                    .cloned()
-                    .collect();
-                let min_val = values_in_bin.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).copied();
-                let max_val = values_in_bin.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).copied();
-                (min_val, max_val, format!("< {:.3}", end), None, Some(*end))
-            },
-            ndhistogram::axis::BinInterval::Overflow { start } => {
-                let values_in_bin: Vec<f64> = numeric_values.iter()
-                    .filter(|&&v| v >= *start)
-                    .cloned()
-                    .collect();
-                let min_val = values_in_bin.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).copied();
-                let max_val = values_in_bin.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).copied();
-                (min_val, max_val, format!(">= {:.3}", start), Some(*start), None)
-            },
-            ndhistogram::axis::BinInterval::Bin { start, end } => {
-                let values_in_bin: Vec<f64> = numeric_values.iter()
-                    .filter(|&&v| v >= *start && v < *end)
-                    .cloned()
-                    .collect();
-                let min_val = values_in_bin.iter().min_by(|a, b| a.partial_cmp(b).unwrap()).copied();
-                let max_val = values_in_bin.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).copied();
-                (min_val, max_val, format!("[{:.3}, {:.3})", start, end), Some(*start), Some(*end))
-            },
+    for (slot_index, slot) in slots.iter().enumerate() {
+        let count = slot.count;
+
+        // Multinomial variance of the count: n * (1 - n/N), with std error sqrt(variance)
+        let n = count as f64;
+        let variance = n * (1.0 - n / total_numeric);
+        let std_error = variance.sqrt();
+
+        let (min_val, max_val) = (slot.min, slot.max);
+
+        // Slot 0 is underflow, the last slot is overflow, everything in between
+        // is a finite bin spanning consecutive edges.
+        let (bin_label, from, to) = if slot_index == 0 {
+            let end = breaks[0];
+            (format!("< {:.3}", end), None, Some(end))
+        } else if slot_index == slots.len() - 1 {
+            let start = breaks[breaks.len() - 1];
+            (format!(">= {:.3}", start), Some(start), None)
+        } else {
+            let start = breaks[slot_index - 1];
+            let end = breaks[slot_index];
+            (format!("[{:.3}, {:.3})", start, end), Some(start), Some(end))
+        };
+
+        // Density and center for --normalize. Only finite bins (both edges
+        // present) get a center and density; the infinite-width underflow and
+        // overflow bins report null density and no center.
+        let (center, density) = if args.normalize {
+            match (from, to) {
+                (Some(a), Some(b)) => {
+                    let width = b - a;
+                    let density = if width > 0.0 {
+                        Some(count as f64 / (total_numeric * width))
+                    } else {
+                        Some(0.0)
+                    };
+                    (Some((a + b) / 2.0), density)
+                },
+                _ => (None, None),
+            }
+        } else {
+            (None, None)
         };
 
         bins.push(NumericHistogramBin {
@@ -289,6 +457,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             count,
             min: min_val,
             max: max_val,
+            variance: Some(variance),
+            std_error: Some(std_error),
+            density,
+            center,
         });
     }
 
@@ -301,6 +473,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             count: null_count,
             min: None,
             max: None,
+            variance: None,
+            std_error: None,
+            density: None,
+            center: None,
         });
     }
 
@@ -308,14 +484,136 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Output results
     let json_output = serde_json::to_string_pretty(&result)?;
+    write_output(&json_output, &args.output)?;
+
+    Ok(())
+}
+
+/// Interpolate the value at percentile `p` (0-100) from an ascending-sorted slice,
+/// linearly blending between the two nearest ranks.
+fn percentile_of(sorted: &[f64], p: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let rank = (p / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let frac = rank - lower as f64;
+    sorted[lower] + frac * (sorted[upper] - sorted[lower])
+}
 
-    if let Some(output_path) = args.output {
-        let mut file = File::create(&output_path)?;
+/// Write the JSON payload to the `--output` file if given, otherwise to stdout.
+fn write_output(json_output: &str, output: &Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(output_path) = output {
+        let mut file = File::create(output_path)?;
         file.write_all(json_output.as_bytes())?;
         eprintln!("Results written to {}", output_path);
     } else {
         println!("{}", json_output);
     }
+    Ok(())
+}
+
+/// Build a sparse categorical histogram (distinct value -> count) for non-numeric
+/// columns, aggregating across every requested file and honouring `--top-n`.
+fn run_categorical(args: &Args, column: &str) -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::HashMap;
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    let mut null_count = 0;
+    let mut total_rows = 0;
+    let mut per_file = Vec::new();
+
+    for file in &args.file {
+        let lf = LazyFrame::scan_parquet(file, Default::default())?
+            .select([col(column)]);
+        let df = lf.collect()?;
+
+        let series = df.column(column)?;
+        let mut file_categorized = 0;
+
+        for i in 0..series.len() {
+            if let Ok(av) = series.get(i) {
+                match av {
+                    AnyValue::Null => {}, // counted via row/category difference below
+                    other => {
+                        let key = match other {
+                            AnyValue::String(s) => s.to_string(),
+                            AnyValue::StringOwned(s) => s.to_string(),
+                            AnyValue::Boolean(b) => b.to_string(),
+                            other => other.to_string(),
+                        };
+                        *counts.entry(key).or_insert(0) += 1;
+                        file_categorized += 1;
+                    },
+                }
+            }
+        }
+
+        let file_rows = df.height();
+        total_rows += file_rows;
+        null_count += file_rows - file_categorized;
+        per_file.push(FileBreakdown {
+            file: file.clone(),
+            total_rows: file_rows,
+            numeric_values: file_categorized,
+            null_values: file_rows - file_categorized,
+        });
+    }
+
+    let categorized = total_rows - null_count;
+    if categorized == 0 {
+        eprintln!("Error: No categorical values found in column '{}'", column);
+        std::process::exit(1);
+    }
+
+    // Sort by descending count, breaking ties by category name for stable output.
+    let mut entries: Vec<(String, usize)> = counts.into_iter().collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    // Collapse the long tail into an "other" bucket when --top-n is set.
+    let other_count = match args.top_n {
+        Some(top_n) if entries.len() > top_n => {
+            entries.split_off(top_n).iter().map(|(_, c)| c).sum()
+        },
+        _ => 0,
+    };
+
+    let total = categorized as f64;
+    let mut bins: Vec<CategoricalHistogramBin> = entries.into_iter()
+        .map(|(category, count)| CategoricalHistogramBin {
+            category,
+            count,
+            fraction: count as f64 / total,
+        })
+        .collect();
+
+    if other_count > 0 {
+        bins.push(CategoricalHistogramBin {
+            category: "other".to_string(),
+            count: other_count,
+            fraction: other_count as f64 / total,
+        });
+    }
+
+    let metadata = HistogramMetadata {
+        file: args.file.join(", "),
+        column: column.to_string(),
+        algorithm: None,
+        num_bins: None,
+        std_dev_size: None,
+        total_rows,
+        numeric_values: categorized,
+        null_values: null_count,
+        bin_edges: Vec::new(),
+        per_file,
+        summary: None,
+    };
+
+    let result = CategoricalHistogramResult { metadata, bins };
+
+    let json_output = serde_json::to_string_pretty(&result)?;
+    write_output(&json_output, &args.output)?;
 
     Ok(())
 }